@@ -1,17 +1,205 @@
+use std::fs;
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 
+use acta::export::{self, ExportFormat};
+use acta::model::{Todo, TodoList, TodoState, DEFAULT_LIST_NAME};
+use acta::storage::Storage;
+
 pub fn parse_args() -> Args {
     Args::parse()
 }
 
-pub fn handle_command(command: Commands) {
+pub fn handle_command(command: Commands, list: Option<String>, storage: &dyn Storage) {
+    let list_name = list.unwrap_or_else(|| DEFAULT_LIST_NAME.to_string());
+
     match command {
-        Commands::List { completed, pending } => println!("Listing"),
-        Commands::Add { todo } => println!("Adding"),
-        Commands::Complete { id } => println!("Completing"),
-        Commands::Edit { id, todo } => println!("Editing"),
-        Commands::Delete { id } => println!("Deleting"),
-        Commands::Export {} => println!("Exporting"),
+        Commands::List {
+            completed,
+            pending,
+            action,
+        } => match action {
+            Some(ListAction::Create { name }) => create_list(storage, name),
+            Some(ListAction::Delete { name }) => delete_list(storage, name),
+            None => list_todos(storage, &list_name, completed, pending),
+        },
+        Commands::Add { todo } => add_todo(storage, &list_name, todo),
+        Commands::Complete { id } => update_todo(storage, &list_name, id, |todo| {
+            todo.state = TodoState::Completed
+        }),
+        Commands::Edit { id, todo } => {
+            update_todo(storage, &list_name, id, |target| target.content = todo)
+        }
+        Commands::Delete { id } => delete_todo(storage, &list_name, id),
+        Commands::Export { format, output } => export_todos(storage, &list_name, format, output),
+    }
+}
+
+fn list_todos(storage: &dyn Storage, list_name: &str, completed: bool, pending: bool) {
+    let Some(list) = load_list(storage, list_name) else {
+        return;
+    };
+
+    for todo in list.todos.iter().filter(|todo| match todo.state {
+        TodoState::Completed => !pending,
+        TodoState::Pending => !completed,
+    }) {
+        println!("[{}] {:?} - {}", todo.id, todo.state, todo.content);
+    }
+}
+
+fn add_todo(storage: &dyn Storage, list_name: &str, content: String) {
+    let Ok(mut store) = storage
+        .load()
+        .inspect_err(|err| eprintln!("Failed to load todos: {err}"))
+    else {
+        return;
+    };
+
+    let Some(list) = store.list_mut(list_name) else {
+        eprintln!("No list found named '{list_name}'");
+        return;
+    };
+
+    let id = list.todos.iter().map(|todo| todo.id).max().unwrap_or(0) + 1;
+    list.todos.push(Todo {
+        id,
+        content,
+        state: TodoState::Pending,
+    });
+
+    if let Err(err) = storage.store(&store) {
+        eprintln!("Failed to save todos: {err}");
+    }
+}
+
+fn delete_todo(storage: &dyn Storage, list_name: &str, id: u64) {
+    let Ok(mut store) = storage
+        .load()
+        .inspect_err(|err| eprintln!("Failed to load todos: {err}"))
+    else {
+        return;
+    };
+
+    let Some(list) = store.list_mut(list_name) else {
+        eprintln!("No list found named '{list_name}'");
+        return;
+    };
+
+    list.todos.retain(|todo| todo.id != id);
+
+    if let Err(err) = storage.store(&store) {
+        eprintln!("Failed to save todos: {err}");
+    }
+}
+
+/// Loads the store and returns a clone of the named list, printing an error
+/// and returning `None` if either the load or the list lookup fails
+fn load_list(storage: &dyn Storage, list_name: &str) -> Option<TodoList> {
+    let store = storage
+        .load()
+        .inspect_err(|err| eprintln!("Failed to load todos: {err}"))
+        .ok()?;
+
+    match store.list(list_name) {
+        Some(list) => Some(list.clone()),
+        None => {
+            eprintln!("No list found named '{list_name}'");
+            None
+        }
+    }
+}
+
+/// Loads the store, applies `update` to the todo matching `id` within the
+/// named list, and persists the result
+fn update_todo(storage: &dyn Storage, list_name: &str, id: u64, update: impl FnOnce(&mut Todo)) {
+    let Ok(mut store) = storage
+        .load()
+        .inspect_err(|err| eprintln!("Failed to load todos: {err}"))
+    else {
+        return;
+    };
+
+    let Some(list) = store.list_mut(list_name) else {
+        eprintln!("No list found named '{list_name}'");
+        return;
+    };
+
+    let Some(todo) = list.todos.iter_mut().find(|todo| todo.id == id) else {
+        eprintln!("No todo found with id {id}");
+        return;
+    };
+    update(todo);
+
+    if let Err(err) = storage.store(&store) {
+        eprintln!("Failed to save todos: {err}");
+    }
+}
+
+/// Renders the named list's todos in `format` and writes the result to
+/// `output`, or to stdout if no path was given
+fn export_todos(
+    storage: &dyn Storage,
+    list_name: &str,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) {
+    let Some(list) = load_list(storage, list_name) else {
+        return;
+    };
+
+    let rendered = match export::render(&list.todos, format) {
+        Ok(rendered) => rendered,
+        Err(err) => {
+            eprintln!("Failed to render export: {err}");
+            return;
+        }
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(err) = fs::write(&path, rendered) {
+                eprintln!("Failed to write export to {}: {err}", path.display());
+            }
+        }
+        None => print!("{rendered}"),
+    }
+}
+
+fn create_list(storage: &dyn Storage, name: String) {
+    let Ok(mut store) = storage
+        .load()
+        .inspect_err(|err| eprintln!("Failed to load todos: {err}"))
+    else {
+        return;
+    };
+
+    if !store.create_list(name.clone()) {
+        eprintln!("A list named '{name}' already exists");
+        return;
+    }
+
+    if let Err(err) = storage.store(&store) {
+        eprintln!("Failed to save todos: {err}");
+    }
+}
+
+fn delete_list(storage: &dyn Storage, name: String) {
+    let Ok(mut store) = storage
+        .load()
+        .inspect_err(|err| eprintln!("Failed to load todos: {err}"))
+    else {
+        return;
+    };
+
+    if !store.delete_list(&name) {
+        eprintln!("No list found named '{name}'");
+        return;
+    }
+
+    if let Err(err) = storage.store(&store) {
+        eprintln!("Failed to save todos: {err}");
     }
 }
 
@@ -20,16 +208,24 @@ pub fn handle_command(command: Commands) {
 pub struct Args {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// The todo list to operate on; defaults to the `default` list
+    #[arg(short, long, global = true)]
+    pub list: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
+    /// Lists todos in the current list, or manages lists via `create`/`delete`
     List {
         #[arg(short, long, default_value_t = false, conflicts_with = "pending")]
         completed: bool,
 
         #[arg(short, long, default_value_t = false, conflicts_with = "completed")]
         pending: bool,
+
+        #[command(subcommand)]
+        action: Option<ListAction>,
     },
     Add {
         #[arg(short, long)]
@@ -50,5 +246,213 @@ pub enum Commands {
         #[arg(short, long)]
         id: u64,
     },
-    Export {},
+    Export {
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: ExportFormat,
+
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+// Subcommands of `List` for managing todo lists themselves (`acta list
+// create`/`acta list delete`) rather than the todos within one
+#[derive(Subcommand, Debug)]
+pub enum ListAction {
+    Create {
+        #[arg(short, long)]
+        name: String,
+    },
+    Delete {
+        #[arg(short, long)]
+        name: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use acta::model::TodoStore;
+    use acta::storage::InMemoryStorage;
+
+    fn todo(id: u64, content: &str, state: TodoState) -> Todo {
+        Todo {
+            id,
+            content: content.to_string(),
+            state,
+        }
+    }
+
+    fn storage_with(todos: Vec<Todo>) -> InMemoryStorage {
+        InMemoryStorage::with_store(TodoStore {
+            lists: vec![TodoList {
+                name: DEFAULT_LIST_NAME.to_string(),
+                todos,
+            }],
+        })
+    }
+
+    fn default_list_todos(storage: &InMemoryStorage) -> Vec<Todo> {
+        storage
+            .load()
+            .expect("load should succeed")
+            .list(DEFAULT_LIST_NAME)
+            .expect("default list should exist")
+            .todos
+            .clone()
+    }
+
+    #[test]
+    fn add_appends_a_pending_todo_with_the_next_id() {
+        let storage = storage_with(vec![todo(1, "existing", TodoState::Pending)]);
+
+        handle_command(
+            Commands::Add {
+                todo: "new".to_string(),
+            },
+            None,
+            &storage,
+        );
+
+        let todos = default_list_todos(&storage);
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[1].id, 2);
+        assert_eq!(todos[1].content, "new");
+        assert!(matches!(todos[1].state, TodoState::Pending));
+    }
+
+    #[test]
+    fn complete_marks_the_matching_todo_completed() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+
+        handle_command(Commands::Complete { id: 1 }, None, &storage);
+
+        let todos = default_list_todos(&storage);
+        assert!(matches!(todos[0].state, TodoState::Completed));
+    }
+
+    #[test]
+    fn complete_with_unknown_id_leaves_the_store_untouched() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+
+        handle_command(Commands::Complete { id: 99 }, None, &storage);
+
+        let todos = default_list_todos(&storage);
+        assert!(matches!(todos[0].state, TodoState::Pending));
+    }
+
+    #[test]
+    fn edit_replaces_the_matching_todos_content() {
+        let storage = storage_with(vec![todo(1, "old", TodoState::Pending)]);
+
+        handle_command(
+            Commands::Edit {
+                id: 1,
+                todo: "new".to_string(),
+            },
+            None,
+            &storage,
+        );
+
+        let todos = default_list_todos(&storage);
+        assert_eq!(todos[0].content, "new");
+    }
+
+    #[test]
+    fn delete_removes_the_matching_todo() {
+        let storage = storage_with(vec![
+            todo(1, "a", TodoState::Pending),
+            todo(2, "b", TodoState::Pending),
+        ]);
+
+        handle_command(Commands::Delete { id: 1 }, None, &storage);
+
+        let todos = default_list_todos(&storage);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].id, 2);
+    }
+
+    #[test]
+    fn delete_with_unknown_id_leaves_the_store_untouched() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+
+        handle_command(Commands::Delete { id: 99 }, None, &storage);
+
+        let todos = default_list_todos(&storage);
+        assert_eq!(todos.len(), 1);
+    }
+
+    #[test]
+    fn list_does_not_modify_the_store() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+
+        handle_command(
+            Commands::List {
+                completed: false,
+                pending: false,
+                action: None,
+            },
+            None,
+            &storage,
+        );
+
+        assert_eq!(default_list_todos(&storage).len(), 1);
+    }
+
+    #[test]
+    fn export_writes_rendered_output_to_the_given_path() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+        let dir = acta::test_support::unique_temp_dir("cli-test");
+        let output_path = dir.join("export.csv");
+
+        handle_command(
+            Commands::Export {
+                format: ExportFormat::Csv,
+                output: Some(output_path.clone()),
+            },
+            None,
+            &storage,
+        );
+
+        let contents = fs::read_to_string(&output_path).expect("output file should exist");
+        assert_eq!(contents, "id,content,state\n1,a,Pending\n");
+        assert_eq!(default_list_todos(&storage).len(), 1);
+
+        fs::remove_dir_all(&dir).expect("failed to clean up test dir");
+    }
+
+    #[test]
+    fn export_against_an_unknown_list_writes_nothing() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+        let dir = acta::test_support::unique_temp_dir("cli-test-missing-list");
+        let output_path = dir.join("export.csv");
+
+        handle_command(
+            Commands::Export {
+                format: ExportFormat::Csv,
+                output: Some(output_path.clone()),
+            },
+            Some("missing".to_string()),
+            &storage,
+        );
+
+        assert!(!output_path.exists());
+
+        fs::remove_dir_all(&dir).expect("failed to clean up test dir");
+    }
+
+    #[test]
+    fn add_against_an_unknown_list_leaves_the_store_untouched() {
+        let storage = storage_with(vec![todo(1, "a", TodoState::Pending)]);
+
+        handle_command(
+            Commands::Add {
+                todo: "new".to_string(),
+            },
+            Some("missing".to_string()),
+            &storage,
+        );
+
+        assert_eq!(default_list_todos(&storage).len(), 1);
+    }
 }
@@ -0,0 +1,96 @@
+//! # Config
+//!
+//! `config` reads user-configurable settings for where and how the todo
+//! store is persisted. The config file lives in the platform config
+//! directory (XDG on Linux, `~/Library/Application Support` on macOS,
+//! `%APPDATA%` on Windows) as resolved by `dirs::config_dir`, e.g.
+//! `~/.config/acta/config.toml` on Linux.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::persistence::ActaError;
+
+/// The directory under the platform config dir that holds acta's config
+const CONFIG_DIR_NAME: &str = "acta";
+/// The config file name within [`CONFIG_DIR_NAME`]
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// The on-disk serialization format for the todo store
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// JSON, the default format
+    #[default]
+    Json,
+    /// TOML
+    Toml,
+}
+
+/// User-configurable settings for where and how the todo store is persisted
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Config {
+    /// Where the store file lives; falls back to `~/.acta` when unset
+    pub storage_path: Option<PathBuf>,
+    /// The serialization format used for the store file
+    #[serde(default)]
+    pub format: Format,
+}
+
+impl Config {
+    /// Loads the config from the platform config directory
+    ///
+    /// Returns the default config (JSON at `~/.acta`) if the platform config
+    /// directory can't be determined or no config file exists there yet.
+    ///
+    /// # Errors
+    ///
+    /// - `ActaError::Io` if the config file exists but cannot be read
+    /// - `ActaError::TomlParse` if the config file contents are malformed
+    pub fn load() -> Result<Config, ActaError> {
+        let Some(path) = config_file_path() else {
+            return Ok(Config::default());
+        };
+
+        load_from(&path)
+    }
+}
+
+/// Loads the config from `path`, falling back to the default config if it
+/// doesn't exist
+fn load_from(path: &Path) -> Result<Config, ActaError> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    Ok(toml::from_str(&contents)?)
+}
+
+/// Returns the path to the config file, if the platform config directory
+/// could be determined
+fn config_file_path() -> Option<PathBuf> {
+    let mut path = dirs::config_dir()?;
+    path.push(CONFIG_DIR_NAME);
+    path.push(CONFIG_FILE_NAME);
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_toml_surfaces_as_toml_parse_error() {
+        let dir = crate::test_support::unique_temp_dir("config-test");
+        let path = dir.join("config.toml");
+        fs::write(&path, "storage_path = [this is not valid toml")
+            .expect("failed to write test config");
+
+        let err = load_from(&path).expect_err("malformed config should fail to parse");
+        assert!(matches!(err, ActaError::TomlParse(_)));
+
+        fs::remove_dir_all(&dir).expect("failed to clean up test dir");
+    }
+}
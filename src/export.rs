@@ -0,0 +1,142 @@
+//! # Export
+//!
+//! `export` renders a todo list into the output formats supported by
+//! `acta export --format`.
+use std::fmt::Write as _;
+
+use clap::ValueEnum;
+
+use crate::model::{Todo, TodoState};
+use crate::persistence::ActaError;
+
+/// The output formats supported by `acta export`
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ExportFormat {
+    /// A GitHub-style Markdown task list
+    Markdown,
+    /// `id,content,state`, quoting fields that contain a comma, quote, or newline
+    Csv,
+    /// Pretty-printed JSON
+    Json,
+}
+
+/// Renders `todos` in the given format
+///
+/// # Errors
+///
+/// - `ActaError::Parse` if `todos` cannot be serialized to JSON (only
+///   reachable for [`ExportFormat::Json`])
+pub fn render(todos: &[Todo], format: ExportFormat) -> Result<String, ActaError> {
+    Ok(match format {
+        ExportFormat::Markdown => render_markdown(todos),
+        ExportFormat::Csv => render_csv(todos),
+        ExportFormat::Json => {
+            let mut rendered = serde_json::to_string_pretty(todos)?;
+            rendered.push('\n');
+            rendered
+        }
+    })
+}
+
+/// Renders `todos` as a GitHub-style Markdown task list
+fn render_markdown(todos: &[Todo]) -> String {
+    let mut output = String::new();
+
+    for todo in todos {
+        let checkbox = match todo.state {
+            TodoState::Completed => "x",
+            TodoState::Pending => " ",
+        };
+        let _ = writeln!(output, "- [{checkbox}] {}", todo.content);
+    }
+
+    output
+}
+
+/// Renders `todos` as CSV with an `id,content,state` header
+fn render_csv(todos: &[Todo]) -> String {
+    let mut output = String::from("id,content,state\n");
+
+    for todo in todos {
+        let _ = writeln!(
+            output,
+            "{},{},{:?}",
+            todo.id,
+            csv_field(&todo.content),
+            todo.state
+        );
+    }
+
+    output
+}
+
+/// Quotes a CSV field if it contains a comma, double quote, or newline,
+/// doubling any embedded double quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: u64, content: &str, state: TodoState) -> Todo {
+        Todo {
+            id,
+            content: content.to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn render_markdown_checks_completed_todos_and_leaves_pending_ones_unchecked() {
+        let todos = vec![
+            todo(1, "done", TodoState::Completed),
+            todo(2, "todo", TodoState::Pending),
+        ];
+
+        let rendered = render(&todos, ExportFormat::Markdown).expect("markdown should render");
+
+        assert_eq!(rendered, "- [x] done\n- [ ] todo\n");
+    }
+
+    #[test]
+    fn render_csv_writes_the_header_and_one_row_per_todo() {
+        let todos = vec![todo(1, "simple", TodoState::Pending)];
+
+        let rendered = render(&todos, ExportFormat::Csv).expect("csv should render");
+
+        assert_eq!(rendered, "id,content,state\n1,simple,Pending\n");
+    }
+
+    #[test]
+    fn render_csv_quotes_a_field_containing_a_comma_a_quote_and_a_newline() {
+        let todos = vec![todo(1, "a, \"b\"\nc", TodoState::Completed)];
+
+        let rendered = render(&todos, ExportFormat::Csv).expect("csv should render");
+
+        assert_eq!(
+            rendered,
+            "id,content,state\n1,\"a, \"\"b\"\"\nc\",Completed\n"
+        );
+    }
+
+    #[test]
+    fn render_json_pretty_prints_with_a_trailing_newline() {
+        let todos = vec![todo(1, "a", TodoState::Pending)];
+
+        let rendered = render(&todos, ExportFormat::Json).expect("json should render");
+
+        assert!(rendered.ends_with('\n'));
+        assert!(rendered.contains("\"content\": \"a\""));
+    }
+
+    #[test]
+    fn csv_field_leaves_a_plain_value_unquoted() {
+        assert_eq!(csv_field("plain"), "plain");
+    }
+}
@@ -0,0 +1,11 @@
+//! # acta
+//!
+//! `acta` is the library crate backing the `acta` CLI. It exposes the data
+//! model, the storage abstraction, and the persistence backends used to read
+//! and write todos.
+pub mod config;
+pub mod export;
+pub mod model;
+pub mod persistence;
+pub mod storage;
+pub mod test_support;
@@ -1,6 +1,7 @@
 mod cli;
 
 use acta::persistence;
+use acta::storage::FileStorage;
 
 use crate::cli::{handle_command, parse_args};
 
@@ -8,9 +9,10 @@ fn main() {
     let args = parse_args();
 
     let _ = persistence::init();
+    let storage = FileStorage;
 
     match args.command {
-        Some(command) => handle_command(command),
+        Some(command) => handle_command(command, args.list, &storage),
         // If no command is passed with default to the TUI flow
         None => panic!("TUI has not yet been implemented"),
     }
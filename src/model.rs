@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 /// Base struct for a todo item
 ///
 /// This will hold the information for a single todo item
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Todo {
     /// Unique identifier
     pub id: u64,
@@ -17,10 +17,150 @@ pub struct Todo {
 }
 
 /// The todo state
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum TodoState {
     /// Todo is pending completion
     Pending,
     /// Todo is completed
     Completed,
 }
+
+/// The name given to the list todos are stored under when none is specified
+pub const DEFAULT_LIST_NAME: &str = "default";
+
+/// A named collection of todos
+///
+/// Lists let users separate, e.g., work and personal todos within a single
+/// store.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TodoList {
+    /// The list's name, unique within a `TodoStore`
+    pub name: String,
+    /// The todos belonging to this list
+    pub todos: Vec<Todo>,
+}
+
+/// Top-level container holding all of a user's todo lists
+///
+/// Deserializes both the current `{"lists": [...]}` shape and the legacy
+/// flat `[...]` array of todos used before lists existed; a legacy array is
+/// migrated into a single list named [`DEFAULT_LIST_NAME`].
+#[derive(Serialize, Debug, Clone)]
+pub struct TodoStore {
+    pub lists: Vec<TodoList>,
+}
+
+impl Default for TodoStore {
+    fn default() -> Self {
+        Self {
+            lists: vec![TodoList {
+                name: DEFAULT_LIST_NAME.to_string(),
+                todos: Vec::new(),
+            }],
+        }
+    }
+}
+
+impl TodoStore {
+    /// Returns the list with the given name, if one exists
+    pub fn list(&self, name: &str) -> Option<&TodoList> {
+        self.lists.iter().find(|list| list.name == name)
+    }
+
+    /// Returns a mutable reference to the list with the given name, if one exists
+    pub fn list_mut(&mut self, name: &str) -> Option<&mut TodoList> {
+        self.lists.iter_mut().find(|list| list.name == name)
+    }
+
+    /// Adds a new, empty list with the given name
+    ///
+    /// Returns `false` without modifying the store if a list with that name
+    /// already exists.
+    pub fn create_list(&mut self, name: String) -> bool {
+        if self.list(&name).is_some() {
+            return false;
+        }
+
+        self.lists.push(TodoList {
+            name,
+            todos: Vec::new(),
+        });
+        true
+    }
+
+    /// Removes the list with the given name
+    ///
+    /// Returns `false` without modifying the store if no such list exists.
+    pub fn delete_list(&mut self, name: &str) -> bool {
+        let original_len = self.lists.len();
+        self.lists.retain(|list| list.name != name);
+        self.lists.len() != original_len
+    }
+}
+
+impl<'de> Deserialize<'de> for TodoStore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Store { lists: Vec<TodoList> },
+            Flat(Vec<Todo>),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Store { lists } => TodoStore { lists },
+            Raw::Flat(todos) => TodoStore {
+                lists: vec![TodoList {
+                    name: DEFAULT_LIST_NAME.to_string(),
+                    todos,
+                }],
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_flat_array_migrates_into_the_default_list() {
+        let store: TodoStore =
+            serde_json::from_str(r#"[{"id":1,"content":"legacy todo","state":"Pending"}]"#)
+                .expect("legacy array should deserialize");
+
+        assert_eq!(store.lists.len(), 1);
+        let list = store
+            .list(DEFAULT_LIST_NAME)
+            .expect("default list should exist");
+        assert_eq!(list.todos.len(), 1);
+        assert_eq!(list.todos[0].content, "legacy todo");
+    }
+
+    #[test]
+    fn store_shape_round_trips_through_json() {
+        let store = TodoStore {
+            lists: vec![TodoList {
+                name: "work".to_string(),
+                todos: vec![Todo {
+                    id: 1,
+                    content: "ship it".to_string(),
+                    state: TodoState::Completed,
+                }],
+            }],
+        };
+
+        let serialized = serde_json::to_string(&store).expect("store should serialize");
+        let deserialized: TodoStore =
+            serde_json::from_str(&serialized).expect("store should deserialize");
+
+        assert_eq!(deserialized.lists.len(), 1);
+        let list = deserialized.list("work").expect("work list should exist");
+        assert_eq!(list.todos.len(), 1);
+        assert_eq!(list.todos[0].content, "ship it");
+        assert!(matches!(list.todos[0].state, TodoState::Completed));
+    }
+}
@@ -1,15 +1,17 @@
 //! # Persistence
 //!
 //! `persistence` contains the functions to manipulate the todo data.
-use std::fs;
+use std::fs::{self, File};
 use std::io;
 use std::path::PathBuf;
 use thiserror::Error;
 
-use crate::model::Todo;
+use crate::config::{Config, Format};
+use crate::model::TodoStore;
 use dirs::home_dir;
 
-/// The default filename for the todo storage in the user's home directory
+/// The default filename for the todo storage in the user's home directory,
+/// used when no `storage_path` is configured
 const FILE_NAME: &str = ".acta";
 
 /// Custom error type for persistence operations
@@ -25,14 +27,21 @@ pub enum ActaError {
     /// Wraps JSON serialization/deserialization errors
     #[error("Parse error: {0}")]
     Parse(#[from] serde_json::Error),
+    /// Wraps TOML deserialization errors, from either the store or the config file
+    #[error("TOML parse error: {0}")]
+    TomlParse(#[from] toml::de::Error),
+    /// Wraps TOML serialization errors
+    #[error("TOML serialize error: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
     /// Indicates that the user's home directory could not be determined
     #[error("Could not find home directory")]
     HomeDir,
 }
 
-/// Initializes the storage file in the home directory
+/// Initializes the storage file at the configured (or default) path
 ///
-/// This function locates the user's home directory and creates the todo
+/// This function resolves the storage location and format from [`Config`],
+/// creates the parent directory if it doesn't exist, and creates the todo
 /// storage file if it doesn't already exist. If the file exists, it leaves
 /// it untouched.
 ///
@@ -42,8 +51,9 @@ pub enum ActaError {
 ///
 /// # Errors
 ///
-/// - `ActaError::HomeDir` if the home directory cannot be determined
-/// - `ActaError::Io` if the file cannot be created
+/// - `ActaError::HomeDir` if no `storage_path` is configured and the home
+///   directory cannot be determined
+/// - `ActaError::Io` if the parent directory or the file cannot be created
 ///
 /// # Examples
 ///
@@ -54,54 +64,69 @@ pub enum ActaError {
 /// println!("Storage initialized at: {:?}", path);
 /// ```
 pub fn init() -> Result<PathBuf, ActaError> {
-    let mut file_path = home_dir().ok_or(ActaError::HomeDir)?;
-    file_path.push(FILE_NAME);
+    let config = Config::load()?;
+    let file_path = storage_path(&config)?;
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
     if !file_path.exists() {
-        fs::write(&file_path, "[]")?;
+        let serialized = serialize(&TodoStore::default(), config.format)?;
+        fs::write(&file_path, serialized)?;
     }
 
     Ok(file_path)
 }
 
-/// Reads the todo items from the default storage file
+/// Reads the todo store from the configured storage file
 ///
-/// This function reads and deserializes the todo list from the storage file.
-/// If the file doesn't exist, it will be created with an empty list via `init()`.
+/// This function reads and deserializes the `TodoStore` from the storage
+/// file, in whichever format is configured. If the file doesn't exist, it
+/// will be created with an empty default list via `init()`. A legacy flat
+/// JSON array (the format used before lists existed) is transparently
+/// migrated into the default list.
 ///
 /// # Returns
 ///
-/// Returns a vector of `Todo` items on success.
+/// Returns the `TodoStore` on success.
 ///
 /// # Errors
 ///
-/// - `ActaError::HomeDir` if the home directory cannot be determined
+/// - `ActaError::HomeDir` if no `storage_path` is configured and the home
+///   directory cannot be determined
 /// - `ActaError::Io` if the file cannot be read
-/// - `ActaError::Parse` if the JSON content is malformed
+/// - `ActaError::Parse` / `ActaError::TomlParse` if the content is malformed
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use acta::persistence::read;
 ///
-/// let todos = read().expect("Failed to read todos");
-/// println!("Found {} todos", todos.len());
+/// let store = read().expect("Failed to read todos");
+/// println!("Found {} lists", store.lists.len());
 /// ```
-pub fn read() -> Result<Vec<Todo>, ActaError> {
+pub fn read() -> Result<TodoStore, ActaError> {
+    let config = Config::load()?;
     let file_path = init()?;
 
-    let serialized_list = fs::read_to_string(file_path)?;
-    Ok(serde_json::from_str(&serialized_list)?)
+    let serialized_store = fs::read_to_string(file_path)?;
+    deserialize(&serialized_store, config.format)
 }
 
-/// Writes todo items to the default storage file
+/// Writes the todo store to the configured storage file
 ///
-/// This function serializes and writes the provided todo list to the storage file,
-/// replacing any existing content. The file will be created if it doesn't exist.
+/// This function serializes the provided `TodoStore` in the configured
+/// format and persists it atomically: the data is written to a temporary
+/// file in the same directory as the target, `fsync`ed to disk, and then
+/// renamed over the real path. Since rename within the same directory is
+/// atomic on POSIX (and via `ReplaceFile`/`MoveFileEx` on Windows), a crash
+/// or power loss mid-write can never leave the storage file truncated or
+/// corrupt; readers either see the old content or the new content.
 ///
 /// # Arguments
 ///
-/// * `todos` - A slice of `Todo` items to persist
+/// * `store` - The `TodoStore` to persist
 ///
 /// # Returns
 ///
@@ -109,24 +134,109 @@ pub fn read() -> Result<Vec<Todo>, ActaError> {
 ///
 /// # Errors
 ///
-/// - `ActaError::HomeDir` if the home directory cannot be determined
-/// - `ActaError::Parse` if the todos cannot be serialized to JSON
-/// - `ActaError::Io` if the file cannot be written
+/// - `ActaError::HomeDir` if no `storage_path` is configured and the home
+///   directory cannot be determined
+/// - `ActaError::Parse` / `ActaError::TomlSerialize` if the store cannot be serialized
+/// - `ActaError::Io` if the temporary file cannot be written or the rename fails
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use acta::persistence::write;
-/// use acta::model::Todo;
+/// use acta::model::TodoStore;
 ///
-/// let todos = vec![/* ... */];
-/// write(&todos).expect("Failed to write todos");
+/// let store = TodoStore::default();
+/// write(&store).expect("Failed to write todos");
 /// ```
-pub fn write(todos: &[Todo]) -> Result<(), ActaError> {
+pub fn write(store: &TodoStore) -> Result<(), ActaError> {
+    let config = Config::load()?;
     let file_path = init()?;
 
-    let serialized_list = serde_json::to_string(&todos)?;
-    fs::write(file_path, serialized_list)?;
+    let serialized_store = serialize(store, config.format)?;
+    write_atomic(&file_path, serialized_store.as_bytes())
+}
+
+/// Resolves the storage file path: the configured `storage_path` if set,
+/// otherwise `~/.acta`
+fn storage_path(config: &Config) -> Result<PathBuf, ActaError> {
+    match &config.storage_path {
+        Some(path) => Ok(path.clone()),
+        None => {
+            let mut file_path = home_dir().ok_or(ActaError::HomeDir)?;
+            file_path.push(FILE_NAME);
+            Ok(file_path)
+        }
+    }
+}
+
+/// Serializes `store` in the given format
+fn serialize(store: &TodoStore, format: Format) -> Result<String, ActaError> {
+    Ok(match format {
+        Format::Json => serde_json::to_string(store)?,
+        Format::Toml => toml::to_string(store)?,
+    })
+}
+
+/// Deserializes a `TodoStore` from `contents` in the given format
+fn deserialize(contents: &str, format: Format) -> Result<TodoStore, ActaError> {
+    Ok(match format {
+        Format::Json => serde_json::from_str(contents)?,
+        Format::Toml => toml::from_str(contents)?,
+    })
+}
+
+/// Writes `contents` to `file_path` atomically
+///
+/// The data is first written to a sibling temporary file (`<file_name>.tmp`) in
+/// the same directory, flushed and `fsync`ed to ensure it has reached disk, and
+/// then moved into place with `fs::rename`. Keeping the temp file alongside the
+/// target guarantees the rename is a same-filesystem operation and therefore
+/// atomic; any temp file left behind by a prior interrupted write is simply
+/// overwritten the next time this runs.
+fn write_atomic(file_path: &PathBuf, contents: &[u8]) -> Result<(), ActaError> {
+    let tmp_path = file_path.with_file_name(format!(
+        "{}.tmp",
+        file_path.file_name().unwrap().to_string_lossy()
+    ));
+
+    let tmp_file = File::create(&tmp_path)?;
+    {
+        use std::io::Write;
+        (&tmp_file).write_all(contents)?;
+        tmp_file.sync_all()?;
+    }
+
+    fs::rename(&tmp_path, file_path)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A stale `.tmp` file left behind by a prior interrupted write must be
+    /// overwritten, not merged with or appended to, by the next write.
+    #[test]
+    fn write_atomic_overwrites_a_stale_tmp_file() {
+        let dir = crate::test_support::unique_temp_dir("write-atomic-test");
+        let file_path = dir.join("todos.toml");
+        let tmp_path = file_path.with_file_name(format!(
+            "{}.tmp",
+            file_path.file_name().unwrap().to_string_lossy()
+        ));
+
+        fs::write(&tmp_path, b"garbage left over from a crash").expect("failed to seed tmp file");
+
+        write_atomic(&file_path, b"{\"lists\":[]}").expect("write_atomic should succeed");
+
+        let contents = fs::read_to_string(&file_path).expect("real file should exist");
+        assert_eq!(contents, "{\"lists\":[]}");
+        assert!(
+            !tmp_path.exists(),
+            "tmp file should be consumed by the rename"
+        );
+
+        fs::remove_dir_all(&dir).expect("failed to clean up test dir");
+    }
+}
@@ -0,0 +1,68 @@
+//! # Storage
+//!
+//! `storage` defines the `Storage` trait that abstracts where the todo store
+//! is loaded from and saved to, decoupling the command layer in `cli` from
+//! any particular on-disk format or location.
+use std::cell::RefCell;
+
+use crate::model::TodoStore;
+use crate::persistence::{self, ActaError};
+
+/// Abstraction over loading and persisting the todo store
+///
+/// Implementors decide how and where the store lives; callers only depend on
+/// this trait, which makes the command layer testable without touching the
+/// real home directory.
+pub trait Storage {
+    /// Loads the current todo store
+    fn load(&self) -> Result<TodoStore, ActaError>;
+    /// Persists `store`, replacing any previously stored data
+    fn store(&self, store: &TodoStore) -> Result<(), ActaError>;
+}
+
+/// Stores todos in a single file at the location and format managed by
+/// [`persistence`] (which honors the user's config, falling back to JSON at
+/// `~/.acta`)
+///
+/// This is the default, production `Storage` implementation.
+#[derive(Default)]
+pub struct FileStorage;
+
+impl Storage for FileStorage {
+    fn load(&self) -> Result<TodoStore, ActaError> {
+        persistence::read()
+    }
+
+    fn store(&self, store: &TodoStore) -> Result<(), ActaError> {
+        persistence::write(store)
+    }
+}
+
+/// Keeps the todo store in memory for the lifetime of the value
+///
+/// Useful for tests that exercise the command layer without touching the
+/// real home directory or filesystem.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    store: RefCell<TodoStore>,
+}
+
+impl InMemoryStorage {
+    /// Creates an in-memory storage pre-seeded with `store`
+    pub fn with_store(store: TodoStore) -> Self {
+        Self {
+            store: RefCell::new(store),
+        }
+    }
+}
+
+impl Storage for InMemoryStorage {
+    fn load(&self) -> Result<TodoStore, ActaError> {
+        Ok(self.store.borrow().clone())
+    }
+
+    fn store(&self, store: &TodoStore) -> Result<(), ActaError> {
+        *self.store.borrow_mut() = store.clone();
+        Ok(())
+    }
+}
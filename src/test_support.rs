@@ -0,0 +1,22 @@
+//! # Test support
+//!
+//! Shared helpers for the `#[cfg(test)]` modules across this crate and the
+//! `acta` binary.
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates and returns a fresh, empty temp directory for a test named
+/// `label`, unique per process and thread so parallel test runs can't
+/// collide on the same path
+///
+/// The caller is responsible for removing the directory once the test is
+/// done with it, via `fs::remove_dir_all`.
+pub fn unique_temp_dir(label: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "acta-{label}-{}-{:?}",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    fs::create_dir_all(&dir).expect("failed to create test dir");
+    dir
+}